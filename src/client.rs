@@ -0,0 +1,75 @@
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Instant;
+
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::io::unix::AsyncFd;
+
+/// One demultiplexed ICMP reply (echo reply or control message), tagged
+/// with the responder's address and arrival time so `Pinger` can compute
+/// RTT and attribute `Time Exceeded`/`Destination Unreachable` replies to
+/// the host that actually sent them.
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub packet: Vec<u8>,
+    pub from: IpAddr,
+    pub when: Instant,
+}
+
+/// The OS-backed [`IcmpTransport`](crate::transport::IcmpTransport): a raw
+/// ICMP socket wrapped in [`AsyncFd`] so `send_to` can be awaited without
+/// blocking the executor.
+#[derive(Clone)]
+pub struct AsyncSocket {
+    inner: Arc<AsyncFd<Socket>>,
+    ipv6: bool,
+}
+
+impl AsyncSocket {
+    /// Open a raw ICMP (v4 or v6, per `domain`) socket.
+    pub fn new(domain: Domain) -> io::Result<AsyncSocket> {
+        let protocol = if domain == Domain::IPV6 {
+            Protocol::ICMPV6
+        } else {
+            Protocol::ICMPV4
+        };
+        let socket = Socket::new(domain, Type::RAW, Some(protocol))?;
+        socket.set_nonblocking(true)?;
+        Ok(AsyncSocket {
+            inner: Arc::new(AsyncFd::new(socket)?),
+            ipv6: domain == Domain::IPV6,
+        })
+    }
+
+    /// Send `buf` to `addr`, returning the number of bytes written.
+    pub async fn send_to(&self, buf: &mut [u8], addr: &SocketAddr) -> io::Result<usize> {
+        loop {
+            let mut guard = self.inner.writable().await?;
+            match guard.try_io(|inner| inner.get_ref().send_to(buf, &(*addr).into())) {
+                Ok(result) => return result,
+                Err(_would_block) => continue,
+            }
+        }
+    }
+
+    /// Set the TTL (IPv4) or hop limit (IPv6) used by subsequent sends.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        let socket = self.inner.get_ref();
+        if self.ipv6 {
+            socket.set_unicast_hops_v6(ttl)
+        } else {
+            socket.set_ttl(ttl)
+        }
+    }
+
+    /// The TTL (IPv4) or hop limit (IPv6) currently configured.
+    pub fn ttl(&self) -> io::Result<u32> {
+        let socket = self.inner.get_ref();
+        if self.ipv6 {
+            socket.unicast_hops_v6()
+        } else {
+            socket.ttl()
+        }
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 use std::io;
+use std::net::IpAddr;
 
 use thiserror::Error;
 
@@ -21,6 +22,12 @@ pub enum SurgeError {
     EchoRequestPacket,
     #[error("Network error.")]
     NetworkError,
+    #[error("Destination unreachable (code {code}) for icmp_seq {seq}, from {from}")]
+    DestinationUnreachable { code: u8, seq: u16, from: IpAddr },
+    #[error("Time exceeded (code {code}) for icmp_seq {seq}, from {from}")]
+    TimeExceeded { code: u8, seq: u16, from: IpAddr },
+    #[error("Parameter problem (code {code}) for icmp_seq {seq}, from {from}")]
+    ParameterProblem { code: u8, seq: u16, from: IpAddr },
 }
 
 #[derive(Error, Debug)]
@@ -0,0 +1,185 @@
+use crate::error::{MalformedPacketError, Result, SurgeError};
+
+pub const ECHO_REPLY: u8 = 0;
+pub const ECHO_REQUEST: u8 = 8;
+pub const DESTINATION_UNREACHABLE: u8 = 3;
+pub const TIME_EXCEEDED: u8 = 11;
+pub const PARAMETER_PROBLEM: u8 = 12;
+
+/// A decoded ICMPv4 message, still addressed by the raw IPv4 datagram it
+/// arrived in.
+#[derive(Debug, Clone)]
+pub struct Icmpv4Packet {
+    pub icmp_type: u8,
+    pub code: u8,
+    icmp: Vec<u8>,
+}
+
+/// Build an ICMPv4 echo request of `size` bytes, checksum included.
+pub fn make_icmpv4_echo_packet(ident: u16, seq_cnt: u16, size: usize) -> Result<Vec<u8>> {
+    if size < 8 {
+        return Err(SurgeError::IncorrectBufferSize);
+    }
+    let mut buf = vec![0u8; size];
+    buf[0] = ECHO_REQUEST;
+    buf[1] = 0;
+    buf[4..6].copy_from_slice(&ident.to_be_bytes());
+    buf[6..8].copy_from_slice(&seq_cnt.to_be_bytes());
+    let sum = checksum(&buf);
+    buf[2..4].copy_from_slice(&sum.to_be_bytes());
+    Ok(buf)
+}
+
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum = 0u32;
+    let mut chunks = data.chunks_exact(2);
+    for chunk in &mut chunks {
+        sum += u32::from(u16::from_be_bytes([chunk[0], chunk[1]]));
+    }
+    if let [last] = *chunks.remainder() {
+        sum += u32::from(last) << 8;
+    }
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+impl Icmpv4Packet {
+    /// Decode the ICMPv4 message carried by a raw IPv4 datagram.
+    pub fn decode(data: &[u8]) -> Result<Icmpv4Packet> {
+        if data.len() < 20 {
+            return Err(SurgeError::MalformedPacket(MalformedPacketError::NotIpv4Packet));
+        }
+        let ihl = usize::from(data[0] & 0x0f) * 4;
+        if data.len() < ihl + 8 {
+            return Err(SurgeError::MalformedPacket(
+                MalformedPacketError::PayloadTooShort {
+                    got: data.len(),
+                    want: ihl + 8,
+                },
+            ));
+        }
+        let icmp = &data[ihl..];
+        Ok(Icmpv4Packet {
+            icmp_type: icmp[0],
+            code: icmp[1],
+            icmp: icmp.to_vec(),
+        })
+    }
+
+    /// Whether this is the echo reply matching `ident`/`seq_cnt`.
+    pub fn check_reply(&self, seq_cnt: u16, ident: u16) -> bool {
+        if self.icmp_type != ECHO_REPLY || self.icmp.len() < 8 {
+            return false;
+        }
+        let got_ident = u16::from_be_bytes([self.icmp[4], self.icmp[5]]);
+        let got_seq = u16::from_be_bytes([self.icmp[6], self.icmp[7]]);
+        got_ident == ident && got_seq == seq_cnt
+    }
+
+    /// If this is a Destination Unreachable / Time Exceeded / Parameter
+    /// Problem message quoting an echo request with the given `ident`,
+    /// return `(icmp_type, code, quoted_seq)`.
+    pub fn control_message(&self, ident: u16) -> Option<(u8, u8, u16)> {
+        if !matches!(
+            self.icmp_type,
+            DESTINATION_UNREACHABLE | TIME_EXCEEDED | PARAMETER_PROBLEM
+        ) {
+            return None;
+        }
+        // Bytes 0..4 are type/code/checksum, 4..8 are unused/pointer, then
+        // the quoted original IPv4 header followed by its first 8 bytes.
+        let quoted = self.icmp.get(8..)?;
+        if quoted.len() < 20 {
+            return None;
+        }
+        let quoted_ihl = usize::from(quoted[0] & 0x0f) * 4;
+        let quoted_icmp = quoted.get(quoted_ihl..quoted_ihl + 8)?;
+        let quoted_ident = u16::from_be_bytes([quoted_icmp[4], quoted_icmp[5]]);
+        let quoted_seq = u16::from_be_bytes([quoted_icmp[6], quoted_icmp[7]]);
+        if quoted_ident != ident {
+            return None;
+        }
+        Some((self.icmp_type, self.code, quoted_seq))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a raw IPv4 datagram (as delivered by a raw ICMPv4 socket)
+    /// carrying a control message that quotes an echo request. `outer_ihl`
+    /// and `quoted_ihl` are IHL word counts (the encoded value is `* 4`
+    /// bytes); `quoted_len` truncates the quoted portion after the header
+    /// to simulate a router that only echoes back a partial datagram.
+    fn control_message_datagram(
+        icmp_type: u8,
+        outer_ihl: u8,
+        quoted_ihl: u8,
+        quoted_ident: u16,
+        quoted_seq: u16,
+        quoted_len: usize,
+    ) -> Vec<u8> {
+        let mut outer = vec![0u8; usize::from(outer_ihl) * 4];
+        outer[0] = 0x40 | outer_ihl;
+
+        let mut icmp = vec![0u8; 8];
+        icmp[0] = icmp_type;
+        icmp[1] = 0;
+
+        let mut quoted = vec![0u8; usize::from(quoted_ihl) * 4];
+        quoted[0] = 0x40 | quoted_ihl;
+        let mut quoted_icmp = vec![0u8; 8];
+        quoted_icmp[0] = ECHO_REQUEST;
+        quoted_icmp[4..6].copy_from_slice(&quoted_ident.to_be_bytes());
+        quoted_icmp[6..8].copy_from_slice(&quoted_seq.to_be_bytes());
+        quoted.extend(quoted_icmp);
+        quoted.truncate(quoted_len);
+
+        let mut datagram = outer;
+        datagram.extend(icmp);
+        datagram.extend(quoted);
+        datagram
+    }
+
+    #[test]
+    fn control_message_quoting_our_echo_decodes() {
+        let datagram = control_message_datagram(DESTINATION_UNREACHABLE, 5, 5, 42, 7, 28);
+        let packet = Icmpv4Packet::decode(&datagram).unwrap();
+        assert_eq!(packet.control_message(42), Some((DESTINATION_UNREACHABLE, 0, 7)));
+    }
+
+    #[test]
+    fn control_message_with_wrong_ident_is_none() {
+        let datagram = control_message_datagram(TIME_EXCEEDED, 5, 5, 42, 7, 28);
+        let packet = Icmpv4Packet::decode(&datagram).unwrap();
+        assert_eq!(packet.control_message(99), None);
+    }
+
+    #[test]
+    fn control_message_with_truncated_quoted_payload_is_none_not_panic() {
+        let mut datagram = control_message_datagram(DESTINATION_UNREACHABLE, 5, 5, 42, 7, 28);
+        datagram.truncate(datagram.len() - 10);
+        let packet = Icmpv4Packet::decode(&datagram).unwrap();
+        assert_eq!(packet.control_message(42), None);
+    }
+
+    #[test]
+    fn control_message_with_oversized_quoted_ihl_is_none_not_panic() {
+        // Claims a 60-byte (max) IHL on a quoted header that's actually
+        // only 20 bytes + 8 bytes of payload: the quoted ICMP slice would
+        // run off the end of the buffer.
+        let datagram = control_message_datagram(PARAMETER_PROBLEM, 5, 15, 42, 7, 28);
+        let packet = Icmpv4Packet::decode(&datagram).unwrap();
+        assert_eq!(packet.control_message(42), None);
+    }
+
+    #[test]
+    fn control_message_with_empty_quoted_payload_is_none_not_panic() {
+        let datagram = control_message_datagram(DESTINATION_UNREACHABLE, 5, 5, 42, 7, 0);
+        let packet = Icmpv4Packet::decode(&datagram).unwrap();
+        assert_eq!(packet.control_message(42), None);
+    }
+}
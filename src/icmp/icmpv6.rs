@@ -0,0 +1,145 @@
+use std::net::Ipv6Addr;
+
+use crate::error::{MalformedPacketError, Result, SurgeError};
+
+pub const ECHO_REQUEST: u8 = 128;
+pub const ECHO_REPLY: u8 = 129;
+pub const DESTINATION_UNREACHABLE: u8 = 1;
+pub const TIME_EXCEEDED: u8 = 3;
+pub const PARAMETER_PROBLEM: u8 = 4;
+
+const IPV6_HEADER_LEN: usize = 40;
+
+/// A decoded ICMPv6 message. Unlike IPv4 raw sockets, an `IPPROTO_ICMPV6`
+/// socket delivers the ICMPv6 header directly, with no IPv6 header
+/// prepended by the kernel.
+#[derive(Debug, Clone)]
+pub struct Icmpv6Packet {
+    pub icmp_type: u8,
+    pub code: u8,
+    icmp: Vec<u8>,
+}
+
+/// Build an ICMPv6 echo request of `size` bytes. The checksum is left at
+/// zero: the kernel computes it over the ICMPv6 pseudo-header for us on
+/// send, since only it knows the source address to use.
+pub fn make_icmpv6_echo_packet(ident: u16, seq_cnt: u16, size: usize) -> Result<Vec<u8>> {
+    if size < 8 {
+        return Err(SurgeError::IncorrectBufferSize);
+    }
+    let mut buf = vec![0u8; size];
+    buf[0] = ECHO_REQUEST;
+    buf[1] = 0;
+    buf[4..6].copy_from_slice(&ident.to_be_bytes());
+    buf[6..8].copy_from_slice(&seq_cnt.to_be_bytes());
+    Ok(buf)
+}
+
+impl Icmpv6Packet {
+    /// Decode an ICMPv6 message. `_destination` is accepted for parity with
+    /// the pseudo-header context a future checksum verification would need.
+    pub fn decode(data: &[u8], _destination: Ipv6Addr) -> Result<Icmpv6Packet> {
+        if data.len() < 8 {
+            return Err(SurgeError::MalformedPacket(MalformedPacketError::NotIcmpv6Packet));
+        }
+        Ok(Icmpv6Packet {
+            icmp_type: data[0],
+            code: data[1],
+            icmp: data.to_vec(),
+        })
+    }
+
+    /// Whether this is the echo reply matching `ident`/`seq_cnt`.
+    pub fn check_reply(&self, seq_cnt: u16, ident: u16) -> bool {
+        if self.icmp_type != ECHO_REPLY || self.icmp.len() < 8 {
+            return false;
+        }
+        let got_ident = u16::from_be_bytes([self.icmp[4], self.icmp[5]]);
+        let got_seq = u16::from_be_bytes([self.icmp[6], self.icmp[7]]);
+        got_ident == ident && got_seq == seq_cnt
+    }
+
+    /// If this is a Destination Unreachable / Time Exceeded / Parameter
+    /// Problem message quoting an echo request with the given `ident`,
+    /// return `(icmp_type, code, quoted_seq)`.
+    pub fn control_message(&self, ident: u16) -> Option<(u8, u8, u16)> {
+        if !matches!(
+            self.icmp_type,
+            DESTINATION_UNREACHABLE | TIME_EXCEEDED | PARAMETER_PROBLEM
+        ) {
+            return None;
+        }
+        // Bytes 0..4 are type/code/checksum, 4..8 are unused/pointer, then
+        // the quoted original (fixed 40-byte) IPv6 header followed by its
+        // first 8 bytes.
+        let quoted = self.icmp.get(8..)?;
+        let quoted_icmp = quoted.get(IPV6_HEADER_LEN..IPV6_HEADER_LEN + 8)?;
+        let quoted_ident = u16::from_be_bytes([quoted_icmp[4], quoted_icmp[5]]);
+        let quoted_seq = u16::from_be_bytes([quoted_icmp[6], quoted_icmp[7]]);
+        if quoted_ident != ident {
+            return None;
+        }
+        Some((self.icmp_type, self.code, quoted_seq))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build an ICMPv6 message (as delivered by a raw ICMPv6 socket)
+    /// carrying a control message that quotes an echo request. `quoted_len`
+    /// is the length the quoted portion (the fixed 40-byte IPv6 header plus
+    /// the 8 echo bytes) is truncated to, to simulate a router that only
+    /// echoes back a partial datagram.
+    fn control_message_datagram(
+        icmp_type: u8,
+        quoted_ident: u16,
+        quoted_seq: u16,
+        quoted_len: usize,
+    ) -> Vec<u8> {
+        let mut icmp = vec![0u8; 8];
+        icmp[0] = icmp_type;
+        icmp[1] = 0;
+
+        let mut quoted = vec![0u8; IPV6_HEADER_LEN];
+        let mut quoted_icmp = vec![0u8; 8];
+        quoted_icmp[0] = ECHO_REQUEST;
+        quoted_icmp[4..6].copy_from_slice(&quoted_ident.to_be_bytes());
+        quoted_icmp[6..8].copy_from_slice(&quoted_seq.to_be_bytes());
+        quoted.extend(quoted_icmp);
+        quoted.truncate(quoted_len);
+
+        let mut datagram = icmp;
+        datagram.extend(quoted);
+        datagram
+    }
+
+    #[test]
+    fn control_message_quoting_our_echo_decodes() {
+        let datagram = control_message_datagram(DESTINATION_UNREACHABLE, 42, 7, IPV6_HEADER_LEN + 8);
+        let packet = Icmpv6Packet::decode(&datagram, Ipv6Addr::LOCALHOST).unwrap();
+        assert_eq!(packet.control_message(42), Some((DESTINATION_UNREACHABLE, 0, 7)));
+    }
+
+    #[test]
+    fn control_message_with_wrong_ident_is_none() {
+        let datagram = control_message_datagram(TIME_EXCEEDED, 42, 7, IPV6_HEADER_LEN + 8);
+        let packet = Icmpv6Packet::decode(&datagram, Ipv6Addr::LOCALHOST).unwrap();
+        assert_eq!(packet.control_message(99), None);
+    }
+
+    #[test]
+    fn control_message_with_truncated_quoted_payload_is_none_not_panic() {
+        let datagram = control_message_datagram(PARAMETER_PROBLEM, 42, 7, IPV6_HEADER_LEN + 4);
+        let packet = Icmpv6Packet::decode(&datagram, Ipv6Addr::LOCALHOST).unwrap();
+        assert_eq!(packet.control_message(42), None);
+    }
+
+    #[test]
+    fn control_message_with_empty_quoted_payload_is_none_not_panic() {
+        let datagram = control_message_datagram(DESTINATION_UNREACHABLE, 42, 7, 0);
+        let packet = Icmpv6Packet::decode(&datagram, Ipv6Addr::LOCALHOST).unwrap();
+        assert_eq!(packet.control_message(42), None);
+    }
+}
@@ -0,0 +1,54 @@
+pub mod icmpv4;
+pub mod icmpv6;
+
+use std::net::IpAddr;
+
+/// A decoded ICMP packet, either flavor.
+#[derive(Debug, Clone)]
+pub enum IcmpPacket {
+    V4(icmpv4::Icmpv4Packet),
+    V6(icmpv6::Icmpv6Packet),
+}
+
+/// An ICMP control (error) message correlated back to one of our own echo
+/// requests via its quoted `(ident, seq)`. The responder's address isn't
+/// carried in the payload itself, so callers attach it from the enclosing
+/// `Message`.
+#[derive(Debug, Clone, Copy)]
+pub enum ControlMessage {
+    DestinationUnreachable { code: u8, seq: u16 },
+    TimeExceeded { code: u8, seq: u16 },
+    ParameterProblem { code: u8, seq: u16 },
+}
+
+impl IcmpPacket {
+    /// Whether this packet is the echo reply matching `seq_cnt`/`ident`.
+    pub fn check_reply_packet(&self, _destination: IpAddr, seq_cnt: u16, ident: u16) -> bool {
+        match self {
+            IcmpPacket::V4(p) => p.check_reply(seq_cnt, ident),
+            IcmpPacket::V6(p) => p.check_reply(seq_cnt, ident),
+        }
+    }
+
+    /// If this packet is a Destination Unreachable / Time Exceeded /
+    /// Parameter Problem message quoting an echo request with the given
+    /// `ident`, decode it into a [`ControlMessage`].
+    pub fn control_message(&self, ident: u16) -> Option<ControlMessage> {
+        let (icmp_type, code, seq) = match self {
+            IcmpPacket::V4(p) => p.control_message(ident)?,
+            IcmpPacket::V6(p) => p.control_message(ident)?,
+        };
+        Some(match self {
+            IcmpPacket::V4(_) => match icmp_type {
+                icmpv4::DESTINATION_UNREACHABLE => ControlMessage::DestinationUnreachable { code, seq },
+                icmpv4::TIME_EXCEEDED => ControlMessage::TimeExceeded { code, seq },
+                _ => ControlMessage::ParameterProblem { code, seq },
+            },
+            IcmpPacket::V6(_) => match icmp_type {
+                icmpv6::DESTINATION_UNREACHABLE => ControlMessage::DestinationUnreachable { code, seq },
+                icmpv6::TIME_EXCEEDED => ControlMessage::TimeExceeded { code, seq },
+                _ => ControlMessage::ParameterProblem { code, seq },
+            },
+        })
+    }
+}
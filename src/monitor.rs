@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+use tokio::time::interval;
+
+use crate::error::SurgeError;
+use crate::ping::Pinger;
+
+/// Reachability state of a monitored target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostState {
+    Reachable,
+    Unreachable,
+}
+
+/// A state-transition event published by a [`Monitor`].
+#[derive(Debug, Clone)]
+pub enum HostEvent {
+    /// The target answered after being unreachable. Targets start out
+    /// reachable, so a first successful reply publishes no event.
+    HostUp { target: IpAddr, rtt: Duration },
+    /// The target has missed `failed_ping_threshold` consecutive pings.
+    HostDown { target: IpAddr },
+}
+
+/// Watches one or more targets on a periodic schedule and publishes
+/// [`HostEvent`]s whenever a target crosses the reachable/unreachable
+/// threshold, turning `Pinger` into a reusable liveness watchdog.
+pub struct Monitor {
+    ping_interval: Duration,
+    ping_timeout: Duration,
+    failed_ping_threshold: u32,
+    events: broadcast::Sender<HostEvent>,
+}
+
+impl Monitor {
+    /// Create a monitor. `failed_ping_threshold` is the number of
+    /// consecutive failed pings required before a target is declared
+    /// `Unreachable` (commonly 3-4).
+    pub fn new(ping_interval: Duration, ping_timeout: Duration, failed_ping_threshold: u32) -> Monitor {
+        let (events, _) = broadcast::channel(64);
+        Monitor {
+            ping_interval,
+            ping_timeout,
+            failed_ping_threshold,
+            events,
+        }
+    }
+
+    /// Subscribe to host up/down events.
+    pub fn subscribe(&self) -> broadcast::Receiver<HostEvent> {
+        self.events.subscribe()
+    }
+
+    /// Watch `pingers` forever, pinging each one every `ping_interval` and
+    /// publishing [`HostEvent`]s as their reachability changes. Runs until
+    /// its enclosing task is dropped or aborted.
+    pub async fn watch(&self, mut pingers: HashMap<IpAddr, Pinger>) {
+        for pinger in pingers.values_mut() {
+            pinger.timeout(self.ping_timeout);
+        }
+
+        let mut state: HashMap<IpAddr, HostState> = pingers
+            .keys()
+            .map(|target| (*target, HostState::Reachable))
+            .collect();
+        let mut consecutive_failures: HashMap<IpAddr, u32> =
+            pingers.keys().map(|target| (*target, 0)).collect();
+
+        let mut ticker = interval(self.ping_interval);
+        let mut seq_cnt: u16 = 0;
+
+        loop {
+            ticker.tick().await;
+
+            for (target, pinger) in pingers.iter_mut() {
+                let result = pinger.ping(seq_cnt).await;
+                self.record(*target, result, &mut state, &mut consecutive_failures);
+            }
+
+            seq_cnt = seq_cnt.wrapping_add(1);
+        }
+    }
+
+    fn record(
+        &self,
+        target: IpAddr,
+        result: Result<(crate::icmp::IcmpPacket, Duration), SurgeError>,
+        state: &mut HashMap<IpAddr, HostState>,
+        consecutive_failures: &mut HashMap<IpAddr, u32>,
+    ) {
+        let failures = consecutive_failures.entry(target).or_insert(0);
+        let current = state.entry(target).or_insert(HostState::Reachable);
+
+        match result {
+            Ok((_, rtt)) => {
+                *failures = 0;
+                if *current == HostState::Unreachable {
+                    *current = HostState::Reachable;
+                    let _ = self.events.send(HostEvent::HostUp { target, rtt });
+                }
+            }
+            Err(SurgeError::Timeout { .. })
+            | Err(SurgeError::NetworkError)
+            | Err(SurgeError::DestinationUnreachable { .. })
+            | Err(SurgeError::TimeExceeded { .. })
+            | Err(SurgeError::ParameterProblem { .. }) => {
+                *failures += 1;
+                if *current == HostState::Reachable && *failures >= self.failed_ping_threshold {
+                    *current = HostState::Unreachable;
+                    let _ = self.events.send(HostEvent::HostDown { target });
+                }
+            }
+            Err(_) => {}
+        }
+    }
+}
@@ -11,15 +11,28 @@ use rand::random;
 use tokio::{
     sync::{broadcast, mpsc},
     task,
-    time::timeout,
+    time::{interval, sleep, timeout},
 };
 
 use crate::client::{AsyncSocket, Message};
 use crate::error::{Result, SurgeError};
-use crate::icmp::{icmpv4, icmpv6, IcmpPacket};
+use crate::icmp::{icmpv4, icmpv6, ControlMessage, IcmpPacket};
+use crate::retry::Backoff;
+use crate::stats::{PingStats, PingSummary};
+use crate::transport::{IcmpReceiver, IcmpTransport};
 
 type Token = (u16, u16);
 
+/// One hop of a [`Pinger::traceroute`] run.
+#[derive(Debug, Clone, Copy)]
+pub struct TracerouteHop {
+    pub ttl: u8,
+    /// The responder's address, or `None` if this hop timed out.
+    pub addr: Option<IpAddr>,
+    /// Round-trip time, only set once the final destination answers.
+    pub rtt: Option<Duration>,
+}
+
 #[derive(Debug, Clone)]
 struct Cache {
     inner: Arc<Mutex<HashMap<Token, Instant>>>,
@@ -57,18 +70,20 @@ impl Cache {
 ///     println!("{:?}", result);
 /// }
 ///
-pub struct Pinger {
+pub struct Pinger<T: IcmpTransport = AsyncSocket, R: IcmpReceiver = mpsc::Receiver<Message>> {
     pub destination: IpAddr,
     pub ident: u16,
     pub size: usize,
     timeout: Duration,
-    socket: AsyncSocket,
-    rx: mpsc::Receiver<Message>,
+    max_attempts: u32,
+    backoff: Backoff,
+    socket: T,
+    rx: R,
     cache: Cache,
     shutdown_notify: broadcast::Sender<()>,
 }
 
-impl Drop for Pinger {
+impl<T: IcmpTransport, R: IcmpReceiver> Drop for Pinger<T, R> {
     fn drop(&mut self) {
         if self.shutdown_notify.send(()).is_err() {
             trace!("notify shutdown error");
@@ -76,18 +91,20 @@ impl Drop for Pinger {
     }
 }
 
-impl Pinger {
+impl<T: IcmpTransport, R: IcmpReceiver> Pinger<T, R> {
     pub(crate) fn new(
         host: IpAddr,
-        socket: AsyncSocket,
-        rx: mpsc::Receiver<Message>,
+        socket: T,
+        rx: R,
         shutdown_notify: broadcast::Sender<()>,
-    ) -> Pinger {
+    ) -> Pinger<T, R> {
         Pinger {
             destination: host,
             ident: random(),
             size: 56,
             timeout: Duration::from_secs(2),
+            max_attempts: 1,
+            backoff: Backoff::default(),
             socket,
             rx,
             cache: Cache::new(),
@@ -95,24 +112,48 @@ impl Pinger {
         }
     }
 
+    /// Build a `Pinger` around a custom transport and receiver, bypassing
+    /// the OS raw socket entirely. This is the hook that makes the crate
+    /// unit-testable: pair an in-memory [`IcmpTransport`] with a matching
+    /// [`IcmpReceiver`] to simulate replies, packet loss, or latency
+    /// without `CAP_NET_RAW` or a real network.
+    pub fn with_transport(host: IpAddr, socket: T, rx: R) -> Pinger<T, R> {
+        let (shutdown_notify, _) = broadcast::channel(1);
+        Pinger::new(host, socket, rx, shutdown_notify)
+    }
+
     /// Set the identification of ICMP.
-    pub fn ident(&mut self, val: u16) -> &mut Pinger {
+    pub fn ident(&mut self, val: u16) -> &mut Pinger<T, R> {
         self.ident = val;
         self
     }
 
     /// Set the packet size.(default: 56)
-    pub fn size(&mut self, size: usize) -> &mut Pinger {
+    pub fn size(&mut self, size: usize) -> &mut Pinger<T, R> {
         self.size = size;
         self
     }
 
     /// The timeout of each Ping, in seconds. (default: 2s)
-    pub fn timeout(&mut self, timeout: Duration) -> &mut Pinger {
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Pinger<T, R> {
         self.timeout = timeout;
         self
     }
 
+    /// Retry on `Timeout`/`NetworkError` up to `max_attempts` times total
+    /// before surfacing the error. (default: 1, i.e. no retry)
+    pub fn retry(&mut self, max_attempts: u32) -> &mut Pinger<T, R> {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Configure the delay between retry attempts. (default: 100ms base,
+    /// doubling, capped at 2s)
+    pub fn backoff(&mut self, backoff: Backoff) -> &mut Pinger<T, R> {
+        self.backoff = backoff;
+        self
+    }
+
     async fn recv_reply(&mut self, seq_cnt: u16) -> Result<(IcmpPacket, Duration)> {
         loop {
             let message = self.rx.recv().await.ok_or(SurgeError::NetworkError)?;
@@ -128,6 +169,28 @@ impl Pinger {
                         if let Some(ins) = self.cache.remove(self.ident, seq_cnt) {
                             return Ok((packet, message.when - ins));
                         }
+                    } else if let Some(control) = packet.control_message(self.ident) {
+                        let seq = match control {
+                            ControlMessage::DestinationUnreachable { seq, .. }
+                            | ControlMessage::ParameterProblem { seq, .. } => seq,
+                            ControlMessage::TimeExceeded { seq, .. } => seq,
+                        };
+                        if seq != seq_cnt {
+                            continue;
+                        }
+                        self.cache.remove(self.ident, seq);
+                        let from = message.from;
+                        return Err(match control {
+                            ControlMessage::DestinationUnreachable { code, seq } => {
+                                SurgeError::DestinationUnreachable { code, seq, from }
+                            }
+                            ControlMessage::TimeExceeded { code, seq } => {
+                                SurgeError::TimeExceeded { code, seq, from }
+                            }
+                            ControlMessage::ParameterProblem { code, seq } => {
+                                SurgeError::ParameterProblem { code, seq, from }
+                            }
+                        });
                     }
                 }
                 Err(SurgeError::EchoRequestPacket) => continue,
@@ -136,8 +199,7 @@ impl Pinger {
         }
     }
 
-    /// Send Ping request with sequence number.
-    pub async fn ping(&mut self, seq_cnt: u16) -> Result<(IcmpPacket, Duration)> {
+    async fn send_once(&mut self, seq_cnt: u16) -> Result<(IcmpPacket, Duration)> {
         let sender = self.socket.clone();
         let mut packet = match self.destination {
             IpAddr::V4(_) => icmpv4::make_icmpv4_echo_packet(self.ident, seq_cnt, self.size)?,
@@ -165,4 +227,170 @@ impl Pinger {
             }
         }
     }
+
+    /// Send Ping request with sequence number, retrying on `Timeout`/
+    /// `NetworkError` with a capped exponential backoff (see [`retry`] and
+    /// [`backoff`]). Each attempt inserts its own fresh send time into the
+    /// cache and cleans up the stale one, so RTT for the successful attempt
+    /// is measured from its own send, not the first.
+    ///
+    /// [`retry`]: Pinger::retry
+    /// [`backoff`]: Pinger::backoff
+    pub async fn ping(&mut self, seq_cnt: u16) -> Result<(IcmpPacket, Duration)> {
+        let mut attempt = 0;
+        loop {
+            match self.send_once(seq_cnt).await {
+                Ok(reply) => return Ok(reply),
+                Err(err @ (SurgeError::Timeout { .. } | SurgeError::NetworkError)) => {
+                    attempt += 1;
+                    if attempt >= self.max_attempts {
+                        return Err(err);
+                    }
+                    sleep(self.backoff.delay(attempt - 1)).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Send echo requests with increasing TTL/hop-limit, collecting the
+    /// `Time Exceeded` responder for each hop until the destination answers
+    /// or `max_hops` is reached. The original TTL is restored before
+    /// returning, whether the traceroute succeeds, fails, or runs out of
+    /// hops, so the `Pinger` remains usable for plain `ping` afterwards.
+    pub async fn traceroute(&mut self, max_hops: u8) -> Result<Vec<TracerouteHop>> {
+        let original_ttl = self.socket.ttl()?;
+        let mut hops = Vec::new();
+
+        let result = async {
+            for ttl in 1..=max_hops {
+                self.socket.set_ttl(ttl as u32)?;
+                let seq_cnt = ttl as u16;
+
+                match self.ping(seq_cnt).await {
+                    Ok((_, rtt)) => {
+                        hops.push(TracerouteHop {
+                            ttl,
+                            addr: Some(self.destination),
+                            rtt: Some(rtt),
+                        });
+                        return Ok(());
+                    }
+                    Err(SurgeError::TimeExceeded { from, .. }) => {
+                        hops.push(TracerouteHop {
+                            ttl,
+                            addr: Some(from),
+                            rtt: None,
+                        });
+                    }
+                    Err(SurgeError::Timeout { .. }) => {
+                        hops.push(TracerouteHop {
+                            ttl,
+                            addr: None,
+                            rtt: None,
+                        });
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+            Ok(())
+        }
+        .await;
+
+        self.socket.set_ttl(original_ttl)?;
+        result.map(|_| hops)
+    }
+
+    /// Run a continuous ping session: send `count` echo requests spaced
+    /// `interval_dur` apart, aggregating the results into a [`PingSummary`]
+    /// the way the `ping(8)` CLI reports them. Timeouts and network errors
+    /// count as lost packets rather than aborting the session.
+    pub async fn measure(&mut self, count: u16, interval_dur: Duration) -> PingSummary {
+        let mut stats = PingStats::new();
+        let mut ticker = interval(interval_dur);
+
+        for seq_cnt in 0..count {
+            ticker.tick().await;
+            let result = self.ping(seq_cnt).await;
+            stats.record_result(&result);
+        }
+
+        stats.summary()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv6Addr;
+
+    use super::*;
+
+    /// An in-memory [`IcmpTransport`] that loops echo requests straight
+    /// back as echo replies over `replies`, bypassing any real socket.
+    /// Set `respond` to `false` to simulate total packet loss.
+    #[derive(Clone)]
+    struct LoopbackTransport {
+        replies: mpsc::Sender<Message>,
+        respond: bool,
+    }
+
+    impl IcmpTransport for LoopbackTransport {
+        fn send_to(
+            &self,
+            buf: &mut [u8],
+            addr: &SocketAddr,
+        ) -> impl std::future::Future<Output = std::io::Result<usize>> + Send {
+            let len = buf.len();
+            let ident = u16::from_be_bytes([buf[4], buf[5]]);
+            let seq_cnt = u16::from_be_bytes([buf[6], buf[7]]);
+            let from = addr.ip();
+            let respond = self.respond;
+            let replies = self.replies.clone();
+            async move {
+                if respond {
+                    let mut reply = vec![0u8; len];
+                    reply[0] = icmpv6::ECHO_REPLY;
+                    reply[4..6].copy_from_slice(&ident.to_be_bytes());
+                    reply[6..8].copy_from_slice(&seq_cnt.to_be_bytes());
+                    let _ = replies
+                        .send(Message {
+                            packet: reply,
+                            from,
+                            when: Instant::now(),
+                        })
+                        .await;
+                }
+                Ok(len)
+            }
+        }
+
+        fn set_ttl(&self, _ttl: u32) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        fn ttl(&self) -> std::io::Result<u32> {
+            Ok(64)
+        }
+    }
+
+    fn loopback_pinger(respond: bool) -> Pinger<LoopbackTransport, mpsc::Receiver<Message>> {
+        let (tx, rx) = mpsc::channel(8);
+        let transport = LoopbackTransport { replies: tx, respond };
+        Pinger::with_transport(IpAddr::V6(Ipv6Addr::LOCALHOST), transport, rx)
+    }
+
+    #[tokio::test]
+    async fn ping_resolves_ok_over_a_mock_transport_with_no_real_socket() {
+        let mut pinger = loopback_pinger(true);
+        let (_, rtt) = pinger.ping(0).await.unwrap();
+        assert!(rtt < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn ping_times_out_when_the_mock_transport_drops_every_reply() {
+        let mut pinger = loopback_pinger(false);
+        pinger.timeout(Duration::from_millis(20));
+        let err = pinger.ping(0).await.unwrap_err();
+        assert!(matches!(err, SurgeError::Timeout { seq: 0 }));
+    }
 }
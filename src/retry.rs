@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use rand::random;
+
+/// Capped exponential backoff between retry attempts, drawing on the
+/// backoff strategy used for karyon's discovery refresh: `delay = min(max,
+/// base * factor^attempt)`, optionally jittered to avoid retry storms.
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    base: Duration,
+    factor: f64,
+    max: Duration,
+    jitter: bool,
+}
+
+impl Backoff {
+    /// `base` is the delay before the first retry, `factor` the growth rate
+    /// per subsequent attempt, and `max` the cap on the computed delay.
+    pub fn new(base: Duration, factor: f64, max: Duration) -> Backoff {
+        Backoff {
+            base,
+            factor,
+            max,
+            jitter: false,
+        }
+    }
+
+    /// Randomize each computed delay by up to 50%, to avoid synchronized
+    /// retries across many pingers.
+    pub fn jitter(mut self, enabled: bool) -> Backoff {
+        self.jitter = enabled;
+        self
+    }
+
+    /// The delay to wait before retry number `attempt` (0-indexed).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let scaled = self.base.as_secs_f64() * self.factor.powi(attempt as i32);
+        let capped = Duration::from_secs_f64(scaled).min(self.max);
+        if self.jitter {
+            capped.mul_f64(0.5 + random::<f64>() * 0.5)
+        } else {
+            capped
+        }
+    }
+}
+
+impl Default for Backoff {
+    /// 100ms base, doubling, capped at 2s, no jitter.
+    fn default() -> Self {
+        Backoff::new(Duration::from_millis(100), 2.0, Duration::from_secs(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_delay_equals_base() {
+        let backoff = Backoff::new(Duration::from_millis(100), 2.0, Duration::from_secs(10));
+        assert_eq!(backoff.delay(0), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn delay_grows_by_factor_each_attempt() {
+        let backoff = Backoff::new(Duration::from_millis(100), 2.0, Duration::from_secs(10));
+        assert_eq!(backoff.delay(1), Duration::from_millis(200));
+        assert_eq!(backoff.delay(2), Duration::from_millis(400));
+        assert_eq!(backoff.delay(3), Duration::from_millis(800));
+    }
+
+    #[test]
+    fn delay_is_capped_at_max() {
+        let backoff = Backoff::new(Duration::from_millis(100), 2.0, Duration::from_millis(300));
+        assert_eq!(backoff.delay(0), Duration::from_millis(100));
+        assert_eq!(backoff.delay(1), Duration::from_millis(200));
+        assert_eq!(backoff.delay(2), Duration::from_millis(300));
+        assert_eq!(backoff.delay(10), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn jitter_stays_within_half_to_full_of_the_capped_delay() {
+        let backoff = Backoff::new(Duration::from_millis(100), 2.0, Duration::from_secs(10)).jitter(true);
+        for attempt in 0..5 {
+            let capped = Duration::from_millis(100).mul_f64(2f64.powi(attempt as i32));
+            let delay = backoff.delay(attempt);
+            assert!(delay >= capped.mul_f64(0.5));
+            assert!(delay <= capped);
+        }
+    }
+}
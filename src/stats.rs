@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use crate::error::SurgeError;
+
+/// Online accumulator for round-trip-time statistics.
+///
+/// Min/avg/max/stddev are computed incrementally with Welford's algorithm, so
+/// memory usage stays `O(1)` no matter how many pings are recorded.
+#[derive(Debug, Clone, Copy)]
+pub struct PingStats {
+    transmitted: usize,
+    received: usize,
+    n: u32,
+    mean: f64,
+    m2: f64,
+    min: Duration,
+    max: Duration,
+}
+
+impl Default for PingStats {
+    fn default() -> Self {
+        PingStats {
+            transmitted: 0,
+            received: 0,
+            n: 0,
+            mean: 0.0,
+            m2: 0.0,
+            min: Duration::ZERO,
+            max: Duration::ZERO,
+        }
+    }
+}
+
+impl PingStats {
+    /// Create an empty accumulator.
+    pub fn new() -> PingStats {
+        PingStats::default()
+    }
+
+    /// Record a successful reply with its round-trip time.
+    pub fn record(&mut self, rtt: Duration) {
+        self.transmitted += 1;
+        self.received += 1;
+
+        let x = rtt.as_secs_f64();
+        self.n += 1;
+        let delta = x - self.mean;
+        self.mean += delta / f64::from(self.n);
+        self.m2 += delta * (x - self.mean);
+
+        if self.n == 1 {
+            self.min = rtt;
+            self.max = rtt;
+        } else {
+            self.min = self.min.min(rtt);
+            self.max = self.max.max(rtt);
+        }
+    }
+
+    /// Record a lost packet (timeout or network error).
+    pub fn record_loss(&mut self) {
+        self.transmitted += 1;
+    }
+
+    /// Number of echo requests sent so far.
+    pub fn transmitted(&self) -> usize {
+        self.transmitted
+    }
+
+    /// Number of echo replies received so far.
+    pub fn received(&self) -> usize {
+        self.received
+    }
+
+    /// Percentage of packets lost, in the range `0.0..=100.0`.
+    pub fn loss_percent(&self) -> f64 {
+        if self.transmitted == 0 {
+            return 0.0;
+        }
+        let lost = self.transmitted - self.received;
+        (lost as f64 / self.transmitted as f64) * 100.0
+    }
+
+    /// The smallest observed round-trip time, if any replies were received.
+    pub fn min(&self) -> Option<Duration> {
+        (self.n > 0).then_some(self.min)
+    }
+
+    /// The mean round-trip time, if any replies were received.
+    pub fn avg(&self) -> Option<Duration> {
+        (self.n > 0).then(|| Duration::from_secs_f64(self.mean))
+    }
+
+    /// The largest observed round-trip time, if any replies were received.
+    pub fn max(&self) -> Option<Duration> {
+        (self.n > 0).then_some(self.max)
+    }
+
+    /// The population standard deviation of the round-trip time, if any
+    /// replies were received.
+    pub fn stddev(&self) -> Option<Duration> {
+        (self.n > 0).then(|| Duration::from_secs_f64((self.m2 / f64::from(self.n)).sqrt()))
+    }
+
+    /// Finalize this accumulator into an immutable [`PingSummary`].
+    pub fn summary(&self) -> PingSummary {
+        PingSummary {
+            transmitted: self.transmitted,
+            received: self.received,
+            loss_percent: self.loss_percent(),
+            min: self.min(),
+            avg: self.avg(),
+            max: self.max(),
+            stddev: self.stddev(),
+        }
+    }
+}
+
+/// A `ping(8)`-style summary of a completed ping session.
+#[derive(Debug, Clone, Copy)]
+pub struct PingSummary {
+    pub transmitted: usize,
+    pub received: usize,
+    pub loss_percent: f64,
+    pub min: Option<Duration>,
+    pub avg: Option<Duration>,
+    pub max: Option<Duration>,
+    pub stddev: Option<Duration>,
+}
+
+impl PingStats {
+    /// Record the outcome of one `Pinger::ping` call.
+    pub(crate) fn record_result<T>(&mut self, result: &Result<(T, Duration), SurgeError>) {
+        match result {
+            Ok((_, rtt)) => self.record(*rtt),
+            Err(_) => self.record_loss(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_stats_report_no_rtt() {
+        let stats = PingStats::new();
+        assert_eq!(stats.transmitted(), 0);
+        assert_eq!(stats.received(), 0);
+        assert_eq!(stats.loss_percent(), 0.0);
+        assert_eq!(stats.min(), None);
+        assert_eq!(stats.avg(), None);
+        assert_eq!(stats.max(), None);
+        assert_eq!(stats.stddev(), None);
+    }
+
+    #[test]
+    fn welford_mean_and_variance_match_textbook_formula() {
+        let samples_ms = [10.0, 20.0, 30.0, 40.0, 50.0];
+        let mut stats = PingStats::new();
+        for ms in samples_ms {
+            stats.record(Duration::from_secs_f64(ms / 1000.0));
+        }
+
+        let n = samples_ms.len() as f64;
+        let mean = samples_ms.iter().sum::<f64>() / n;
+        let variance = samples_ms.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+
+        assert_eq!(stats.min(), Some(Duration::from_secs_f64(0.010)));
+        assert_eq!(stats.max(), Some(Duration::from_secs_f64(0.050)));
+        // Tolerances are loose enough to absorb `Duration`'s nanosecond
+        // quantization, which dominates the error at millisecond scale,
+        // while still being far tighter than any real RTT jitter.
+        assert!((stats.avg().unwrap().as_secs_f64() * 1000.0 - mean).abs() < 1e-4);
+        assert!(
+            (stats.stddev().unwrap().as_secs_f64() * 1000.0 - variance.sqrt()).abs() < 1e-4
+        );
+    }
+
+    #[test]
+    fn losses_count_toward_transmitted_but_not_received() {
+        let mut stats = PingStats::new();
+        stats.record(Duration::from_millis(10));
+        stats.record_loss();
+        stats.record_loss();
+
+        assert_eq!(stats.transmitted(), 3);
+        assert_eq!(stats.received(), 1);
+        assert!((stats.loss_percent() - 200.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn record_result_counts_timeouts_and_other_errors_as_loss() {
+        let mut stats = PingStats::new();
+        stats.record_result::<()>(&Err(SurgeError::Timeout { seq: 0 }));
+        stats.record_result::<()>(&Err(SurgeError::NetworkError));
+        stats.record_result(&Ok(((), Duration::from_millis(5))));
+
+        assert_eq!(stats.transmitted(), 3);
+        assert_eq!(stats.received(), 1);
+    }
+}
@@ -0,0 +1,62 @@
+use std::io;
+use std::net::SocketAddr;
+
+use crate::client::{AsyncSocket, Message};
+
+/// Abstraction over the transport used to send ICMP echo requests.
+///
+/// `Pinger` is generic over this trait rather than hard-wired to
+/// [`AsyncSocket`], so callers can inject an in-memory loopback transport
+/// for deterministic tests, route ICMP over an alternative stack, or
+/// simulate packet loss/latency, without changing the public `ping`
+/// ergonomics or requiring `CAP_NET_RAW`.
+pub trait IcmpTransport: Clone + Send + Sync + 'static {
+    /// Send `buf` to `addr`, returning the number of bytes written.
+    fn send_to(
+        &self,
+        buf: &mut [u8],
+        addr: &SocketAddr,
+    ) -> impl std::future::Future<Output = io::Result<usize>> + Send;
+
+    /// Set the TTL (IPv4) or hop limit (IPv6) used by subsequent sends.
+    /// Used by `Pinger::traceroute` to probe one hop at a time.
+    fn set_ttl(&self, ttl: u32) -> io::Result<()>;
+
+    /// The TTL (IPv4) or hop limit (IPv6) currently configured. Used by
+    /// `Pinger::traceroute` to restore the original value once it's done
+    /// probing hops.
+    fn ttl(&self) -> io::Result<u32>;
+}
+
+impl IcmpTransport for AsyncSocket {
+    fn send_to(
+        &self,
+        buf: &mut [u8],
+        addr: &SocketAddr,
+    ) -> impl std::future::Future<Output = io::Result<usize>> + Send {
+        AsyncSocket::send_to(self, buf, addr)
+    }
+
+    fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        AsyncSocket::set_ttl(self, ttl)
+    }
+
+    fn ttl(&self) -> io::Result<u32> {
+        AsyncSocket::ttl(self)
+    }
+}
+
+/// Abstraction over the receive half of a ping session: the demultiplexed
+/// stream of [`Message`]s destined for one `Pinger`. Split out from
+/// [`IcmpTransport`] (mirroring the `UdpSender`/`UdpReceiver` split this
+/// design is modeled on) so a mock transport's replies can reach
+/// `Pinger::ping` without a real OS socket and background dispatcher.
+pub trait IcmpReceiver: Send + 'static {
+    fn recv(&mut self) -> impl std::future::Future<Output = Option<Message>> + Send;
+}
+
+impl IcmpReceiver for tokio::sync::mpsc::Receiver<Message> {
+    fn recv(&mut self) -> impl std::future::Future<Output = Option<Message>> + Send {
+        tokio::sync::mpsc::Receiver::recv(self)
+    }
+}